@@ -0,0 +1,108 @@
+//! Programmable extension points for library users. The CLI has no flag
+//! for any of these -- there's no way to hand a trait implementation
+//! across a command line -- but a crate embedding `bindgen::Builder`
+//! directly can register a `ParseCallbacks` implementation, consulted by
+//! `gen` at each naming/derive decision point, to customize generation
+//! beyond what the fixed `remove_prefix`/`derive_debug`/`macro_int_types`
+//! knobs allow. Nothing calls `int_macro`: this crate does not convert
+//! macros to constants.
+
+/// The C integer type a macro's value should be typed as, mirroring
+/// `--macro-int-types`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntKind {
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    U64,
+    I64,
+}
+
+/// Context passed to `add_derives`, describing the item a derive is being
+/// considered for.
+#[derive(Debug, Clone)]
+pub struct DeriveInfo<'a> {
+    pub name: &'a str,
+}
+
+/// Hooks consulted by `gen` at each naming/derive decision point. Register
+/// an implementation with `Builder::parse_callbacks`.
+pub trait ParseCallbacks: ::std::fmt::Debug {
+    /// Called for every type, function and variable name `gen` is about to
+    /// emit. Returning `Some(name)` renames the item; `None` keeps the name
+    /// bindgen would otherwise have chosen. Not consulted for the
+    /// `dynamic_library_name` wrapper struct or for the declarations
+    /// `wrap_static_fns` adds.
+    fn item_name(&self, _original_name: &str) -> Option<String> {
+        None
+    }
+
+    /// Called for every enum variant, so e.g. a shared `FOO_BAR_BAZ`
+    /// prefix can be stripped from `BAZ`'s variant name.
+    fn enum_variant_name(
+        &self,
+        _enum_name: &str,
+        _variant_name: &str,
+        _variant_value: i64,
+    ) -> Option<String> {
+        None
+    }
+
+    /// Called once per item that would receive a derive, to let users add
+    /// further derives on top of bindgen's defaults.
+    fn add_derives(&self, _info: &DeriveInfo) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Hook for steering the integer type of a macro converted to a
+    /// constant, generalizing `--macro-int-types`. Never called: this crate
+    /// does not convert macros to constants.
+    fn int_macro(&self, _name: &str, _value: i64) -> Option<IntKind> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct StripPrefix;
+
+    impl ParseCallbacks for StripPrefix {
+        fn item_name(&self, original_name: &str) -> Option<String> {
+            original_name.strip_prefix("lib_").map(|s| s.to_string())
+        }
+
+        fn add_derives(&self, _info: &DeriveInfo) -> Vec<String> {
+            vec!["Hash".to_string()]
+        }
+    }
+
+    #[test]
+    fn default_callbacks_are_inert() {
+        #[derive(Debug)]
+        struct Noop;
+        impl ParseCallbacks for Noop {}
+
+        let cb = Noop;
+        assert_eq!(cb.item_name("foo"), None);
+        assert_eq!(cb.enum_variant_name("E", "V", 0), None);
+        assert!(cb.add_derives(&DeriveInfo { name: "Foo" }).is_empty());
+        assert_eq!(cb.int_macro("FOO", 1), None);
+    }
+
+    #[test]
+    fn custom_callbacks_rename_and_add_derives() {
+        let cb = StripPrefix;
+        assert_eq!(cb.item_name("lib_foo"), Some("foo".to_string()));
+        assert_eq!(cb.item_name("other"), None);
+        assert_eq!(
+            cb.add_derives(&DeriveInfo { name: "lib_foo" }),
+            vec!["Hash".to_string()]
+        );
+    }
+}
@@ -0,0 +1,43 @@
+//! Writes a `make`-format depfile listing every header transitively
+//! `#include`d while parsing, so build systems know to re-run bindgen
+//! whenever any of them changes.
+
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+
+/// Formats `<output>: header1.h header2.h ...` from a de-duplicated,
+/// sorted view of `files`.
+pub fn format(output_path: &str, files: &[String]) -> String {
+    let unique: BTreeSet<&str> = files.iter().map(|s| s.as_str()).collect();
+    let mut line = format!("{}:", output_path);
+    for file in unique {
+        line.push(' ');
+        line.push_str(file);
+    }
+    line.push('\n');
+    line
+}
+
+/// Writes the depfile for `files` to `depfile_path`.
+pub fn write<P: AsRef<Path>>(depfile_path: P, output_path: &str, files: &[String]) -> io::Result<()> {
+    let mut f = File::create(depfile_path)?;
+    f.write_all(format(output_path, files).as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_sorted_deduplicated_rule() {
+        let files = vec!["b.h".to_string(), "a.h".to_string(), "b.h".to_string()];
+        assert_eq!(format("out.rs", &files), "out.rs: a.h b.h\n");
+    }
+
+    #[test]
+    fn empty_file_list_still_has_a_target() {
+        assert_eq!(format("out.rs", &[]), "out.rs:\n");
+    }
+}
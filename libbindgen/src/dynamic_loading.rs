@@ -0,0 +1,132 @@
+//! A codegen backend parallel to the normal static/`extern "C"` link path:
+//! instead of emitting declarations that the linker resolves, emit a
+//! struct that `dlopen`s the library at runtime and holds one function
+//! pointer per allowlisted function, resolved by `new`.
+
+use ir::Global;
+
+fn params_decl(g: &Global) -> String {
+    g.params
+        .iter()
+        .map(|p| format!("{}: {}", p.name, p.ty))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn params_call(g: &Global) -> String {
+    g.params
+        .iter()
+        .map(|p| p.name.clone())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn fn_ptr_ty(g: &Global) -> String {
+    if g.ret_ty.is_empty() || g.ret_ty == "void" {
+        format!("unsafe extern \"C\" fn({})", params_decl(g))
+    } else {
+        format!("unsafe extern \"C\" fn({}) -> {}", params_decl(g), g.ret_ty)
+    }
+}
+
+/// Emits the `struct_name` dlopen wrapper for `functions`. With
+/// `require_all` off, a missing symbol doesn't fail the whole `new` call --
+/// it's stored as an `Err` so the rest of a partially-available library
+/// still loads.
+pub fn emit(struct_name: &str, functions: &[Global], require_all: bool) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("pub struct {} {{\n", struct_name));
+    out.push_str("    __library: ::libloading::Library,\n");
+    for f in functions {
+        if require_all {
+            out.push_str(&format!("    pub {}: {},\n", f.name, fn_ptr_ty(f)));
+        } else {
+            out.push_str(&format!(
+                "    pub {}: Result<{}, ::libloading::Error>,\n",
+                f.name,
+                fn_ptr_ty(f)
+            ));
+        }
+    }
+    out.push_str("}\n");
+
+    out.push_str(&format!("impl {} {{\n", struct_name));
+    out.push_str("    pub unsafe fn new<P: AsRef<::std::ffi::OsStr>>(path: P) -> Result<Self, ::libloading::Error> {\n");
+    out.push_str("        let __library = ::libloading::Library::new(path)?;\n");
+    for f in functions {
+        if require_all {
+            out.push_str(&format!(
+                "        let {name} = *__library.get::<{ty}>(b\"{name}\\0\")?;\n",
+                name = f.name,
+                ty = fn_ptr_ty(f)
+            ));
+        } else {
+            out.push_str(&format!(
+                "        let {name} = __library.get::<{ty}>(b\"{name}\\0\").map(|sym| *sym);\n",
+                name = f.name,
+                ty = fn_ptr_ty(f)
+            ));
+        }
+    }
+    out.push_str(&format!("        Ok({} {{\n", struct_name));
+    out.push_str("            __library: __library,\n");
+    for f in functions {
+        out.push_str(&format!("            {name}: {name},\n", name = f.name));
+    }
+    out.push_str("        })\n    }\n");
+
+    for f in functions {
+        let call = format!("({}.{})({})", "self", f.name, params_call(f));
+        let ret = if f.ret_ty.is_empty() || f.ret_ty == "void" {
+            String::new()
+        } else {
+            format!(" -> {}", f.ret_ty)
+        };
+        if require_all {
+            out.push_str(&format!(
+                "    pub unsafe fn {name}(&self, {params}){ret} {{ {call} }}\n",
+                name = f.name,
+                params = params_decl(f),
+                ret = ret,
+                call = call
+            ));
+        } else {
+            out.push_str(&format!(
+                "    pub unsafe fn {name}(&self, {params}){ret} {{ (self.{name}.as_ref().unwrap())({args}) }}\n",
+                name = f.name,
+                params = params_decl(f),
+                ret = ret,
+                args = params_call(f)
+            ));
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ir::{Global, ItemKind, Param};
+
+    fn sample_fn() -> Global {
+        let mut g = Global::new(ItemKind::Function, "do_thing", "test.h");
+        g.ret_ty = "i32".to_string();
+        g.params = vec![Param { name: "x".into(), ty: "i32".into() }];
+        g
+    }
+
+    #[test]
+    fn require_all_stores_bare_fn_pointers() {
+        let code = emit("Lib", &[sample_fn()], true);
+        assert!(code.contains("pub do_thing: unsafe extern \"C\" fn(x: i32) -> i32,"));
+        assert!(code.contains("let do_thing = *__library.get"));
+    }
+
+    #[test]
+    fn optional_symbols_store_result() {
+        let code = emit("Lib", &[sample_fn()], false);
+        assert!(code.contains("pub do_thing: Result<unsafe extern \"C\" fn(x: i32) -> i32, ::libloading::Error>,"));
+        assert!(code.contains(".map(|sym| *sym);"));
+    }
+}
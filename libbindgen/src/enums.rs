@@ -0,0 +1,140 @@
+//! Enum codegen: C enums can come out as a real Rust `enum`, as bare
+//! `const`s, or -- for enums that legitimately carry out-of-range
+//! discriminants (flags, sentinels) -- as a `#[repr(transparent)]`
+//! newtype, optionally with `BitOr`/`BitAnd` for bitflag-style enums.
+
+use ir::Global;
+use regex_set::RegexSet;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnumStyle {
+    Rust,
+    Constants,
+    Newtype,
+    Bitfield,
+}
+
+/// Per-name enum style selection: `--bitfield-enum` wins over
+/// `--newtype-enum`, which wins over the blanket `--no-rust-enums`/
+/// `rust_enums` setting.
+pub fn choose_style(
+    name: &str,
+    rust_enums: bool,
+    newtype_enum: &RegexSet,
+    bitfield_enum: &RegexSet,
+) -> EnumStyle {
+    if bitfield_enum.is_match(name) {
+        EnumStyle::Bitfield
+    } else if newtype_enum.is_match(name) {
+        EnumStyle::Newtype
+    } else if rust_enums {
+        EnumStyle::Rust
+    } else {
+        EnumStyle::Constants
+    }
+}
+
+/// Joins the built-in derive set with whatever a registered `ParseCallbacks`
+/// added on top, for the `#[derive(...)]` attribute on `style`'s type.
+fn derive_attr(extra_derives: &[String]) -> String {
+    let mut derives = vec!["Debug", "Copy", "Clone", "PartialEq", "Eq"];
+    derives.extend(extra_derives.iter().map(String::as_str));
+    format!("#[derive({})]\n", derives.join(", "))
+}
+
+/// Renders an enum `Global` as Rust source, according to `style`.
+pub fn emit(g: &Global, style: EnumStyle, repr_ty: &str, extra_derives: &[String]) -> String {
+    match style {
+        EnumStyle::Rust => {
+            let mut out = format!("#[repr({})]\n{}pub enum {} {{\n", repr_ty, derive_attr(extra_derives), g.name);
+            for v in &g.variants {
+                out.push_str(&format!("    {} = {},\n", v.name, v.value));
+            }
+            out.push_str("}\n");
+            out
+        }
+        EnumStyle::Constants => {
+            let mut out = String::new();
+            for v in &g.variants {
+                out.push_str(&format!(
+                    "pub const {}: {} = {};\n",
+                    v.name, repr_ty, v.value
+                ));
+            }
+            out
+        }
+        EnumStyle::Newtype | EnumStyle::Bitfield => {
+            let mut out = format!(
+                "#[repr(transparent)]\n{}pub struct {}(pub {});\nimpl {} {{\n",
+                derive_attr(extra_derives), g.name, repr_ty, g.name
+            );
+            for v in &g.variants {
+                out.push_str(&format!(
+                    "    pub const {}: {} = {}({});\n",
+                    v.name, g.name, g.name, v.value
+                ));
+            }
+            out.push_str("}\n");
+            if style == EnumStyle::Bitfield {
+                out.push_str(&format!(
+                    "impl ::std::ops::BitOr for {name} {{\n    type Output = Self;\n    fn bitor(self, rhs: Self) -> Self {{ {name}(self.0 | rhs.0) }}\n}}\n",
+                    name = g.name
+                ));
+                out.push_str(&format!(
+                    "impl ::std::ops::BitAnd for {name} {{\n    type Output = Self;\n    fn bitand(self, rhs: Self) -> Self {{ {name}(self.0 & rhs.0) }}\n}}\n",
+                    name = g.name
+                ));
+            }
+            out
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ir::{EnumVariant, Global, ItemKind};
+
+    fn sample_enum() -> Global {
+        let mut g = Global::new(ItemKind::Enum, "Color", "test.h");
+        g.variants = vec![
+            EnumVariant { name: "RED".into(), value: 0 },
+            EnumVariant { name: "GREEN".into(), value: 1 },
+        ];
+        g
+    }
+
+    #[test]
+    fn bitfield_wins_over_newtype() {
+        let newtype = RegexSet::new(["Color"]).unwrap();
+        let bitfield = RegexSet::new(["Color"]).unwrap();
+        let style = choose_style("Color", true, &newtype, &bitfield);
+        assert_eq!(style, EnumStyle::Bitfield);
+    }
+
+    #[test]
+    fn default_is_rust_enum_unless_disabled() {
+        let empty = RegexSet::new(Vec::<String>::new()).unwrap();
+        assert_eq!(choose_style("Color", true, &empty, &empty), EnumStyle::Rust);
+        assert_eq!(
+            choose_style("Color", false, &empty, &empty),
+            EnumStyle::Constants
+        );
+    }
+
+    #[test]
+    fn newtype_emits_transparent_struct_with_consts() {
+        let code = emit(&sample_enum(), EnumStyle::Newtype, "u32", &[]);
+        assert!(code.contains("#[repr(transparent)]"));
+        assert!(code.contains("pub struct Color(pub u32);"));
+        assert!(code.contains("pub const RED: Color = Color(0);"));
+        assert!(!code.contains("BitOr"));
+    }
+
+    #[test]
+    fn bitfield_also_emits_bit_operators() {
+        let code = emit(&sample_enum(), EnumStyle::Bitfield, "u32", &[]);
+        assert!(code.contains("impl ::std::ops::BitOr for Color"));
+        assert!(code.contains("impl ::std::ops::BitAnd for Color"));
+    }
+}
@@ -0,0 +1,171 @@
+//! The allowlist/blocklist filtering pass: walks the parsed `Global` list
+//! and decides which items survive into codegen, pulling in any type
+//! reachable from an allowlisted item so the output still compiles.
+
+use std::collections::{HashMap, HashSet};
+
+use ir::{Global, ItemKind};
+use regex_set::RegexSet;
+
+/// The compiled allow/blocklist regex sets, as stored on `BindgenOptions`.
+#[derive(Debug, Default)]
+pub struct FilterOptions {
+    pub allowlist_functions: RegexSet,
+    pub allowlist_types: RegexSet,
+    pub allowlist_vars: RegexSet,
+    pub blocklist_types: RegexSet,
+    pub blocklist_functions: RegexSet,
+    pub blocklist_items: RegexSet,
+    pub blocklist_files: RegexSet,
+}
+
+impl FilterOptions {
+    fn has_any_allowlist(&self) -> bool {
+        !self.allowlist_functions.is_empty() || !self.allowlist_types.is_empty()
+            || !self.allowlist_vars.is_empty()
+    }
+
+    fn is_directly_allowlisted(&self, g: &Global) -> bool {
+        match g.kind {
+            ItemKind::Function => self.allowlist_functions.is_match(&g.name),
+            ItemKind::Var => self.allowlist_vars.is_match(&g.name),
+            ItemKind::Struct | ItemKind::Enum => self.allowlist_types.is_match(&g.name),
+        }
+    }
+
+    fn is_blocklisted(&self, g: &Global) -> bool {
+        if self.blocklist_items.is_match(&g.name) {
+            return true;
+        }
+        if self.blocklist_files.is_match(&g.file) {
+            return true;
+        }
+        match g.kind {
+            ItemKind::Function => self.blocklist_functions.is_match(&g.name),
+            ItemKind::Struct | ItemKind::Enum => self.blocklist_types.is_match(&g.name),
+            ItemKind::Var => false,
+        }
+    }
+}
+
+/// The outcome of the filtering pass for a single item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Disposition {
+    /// Emit the item in full.
+    Keep,
+    /// Emit the item as an opaque stub: it was blocklisted, but something
+    /// that survived still references it.
+    Opaque,
+}
+
+/// Runs the filtering pass, returning the surviving items paired with
+/// their disposition, in their original order.
+pub fn filter_globals(globals: &[Global], opts: &FilterOptions) -> Vec<(Global, Disposition)> {
+    let by_name: HashMap<&str, &Global> = globals.iter().map(|g| (g.name.as_str(), g)).collect();
+
+    let roots_unconstrained = !opts.has_any_allowlist();
+
+    let mut keep: HashSet<String> = HashSet::new();
+    let mut opaque: HashSet<String> = HashSet::new();
+    let mut worklist: Vec<String> = Vec::new();
+
+    for g in globals {
+        if opts.is_blocklisted(g) {
+            continue;
+        }
+        if (roots_unconstrained || opts.is_directly_allowlisted(g)) && keep.insert(g.name.clone()) {
+            worklist.push(g.name.clone());
+        }
+    }
+
+    // Transitively pull in anything a kept item references.
+    while let Some(name) = worklist.pop() {
+        let g = match by_name.get(name.as_str()) {
+            Some(g) => g,
+            None => continue,
+        };
+        for referenced in &g.referenced {
+            if keep.contains(referenced) || opaque.contains(referenced) {
+                continue;
+            }
+            let target = match by_name.get(referenced.as_str()) {
+                Some(t) => t,
+                None => continue,
+            };
+            if opts.is_blocklisted(target) {
+                opaque.insert(referenced.clone());
+            } else {
+                keep.insert(referenced.clone());
+                worklist.push(referenced.clone());
+            }
+        }
+    }
+
+    globals
+        .iter()
+        .filter_map(|g| {
+            if keep.contains(&g.name) {
+                Some((g.clone(), Disposition::Keep))
+            } else if opaque.contains(&g.name) {
+                Some((g.clone(), Disposition::Opaque))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ir::{Global, ItemKind};
+
+    fn func(name: &str, refs: &[&str]) -> Global {
+        let mut g = Global::new(ItemKind::Function, name, "test.h");
+        g.referenced = refs.iter().map(|s| s.to_string()).collect();
+        g
+    }
+
+    fn ty(name: &str) -> Global {
+        Global::new(ItemKind::Struct, name, "test.h")
+    }
+
+    #[test]
+    fn no_allowlist_keeps_everything_not_blocklisted() {
+        let globals = vec![func("foo", &[]), func("bar", &[])];
+        let opts = FilterOptions {
+            blocklist_functions: RegexSet::new(["bar"]).unwrap(),
+            ..FilterOptions::default()
+        };
+        let kept = filter_globals(&globals, &opts);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].0.name, "foo");
+    }
+
+    #[test]
+    fn allowlist_pulls_in_referenced_types() {
+        let globals = vec![func("foo", &["Point"]), ty("Point"), ty("Unused")];
+        let opts = FilterOptions {
+            allowlist_functions: RegexSet::new(["foo"]).unwrap(),
+            ..FilterOptions::default()
+        };
+        let kept = filter_globals(&globals, &opts);
+        let names: HashSet<_> = kept.iter().map(|(g, _)| g.name.clone()).collect();
+        assert!(names.contains("foo"));
+        assert!(names.contains("Point"));
+        assert!(!names.contains("Unused"));
+    }
+
+    #[test]
+    fn blocklisted_but_referenced_type_is_opaque() {
+        let globals = vec![func("foo", &["Secret"]), ty("Secret")];
+        let opts = FilterOptions {
+            allowlist_functions: RegexSet::new(["foo"]).unwrap(),
+            blocklist_types: RegexSet::new(["Secret"]).unwrap(),
+            ..FilterOptions::default()
+        };
+        let kept = filter_globals(&globals, &opts);
+        let secret = kept.iter().find(|(g, _)| g.name == "Secret").unwrap();
+        assert_eq!(secret.1, Disposition::Opaque);
+    }
+}
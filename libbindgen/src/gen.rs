@@ -0,0 +1,289 @@
+//! Ties the filtered `Global` list together with the per-kind codegen
+//! (enums, functions, opaque types) into the flattened `Item` list that
+//! the optional post-processing pass and final printing consume.
+
+use callbacks::{DeriveInfo, ParseCallbacks};
+use enums;
+use filter::{self, Disposition, FilterOptions};
+use ir::{Global, ItemKind};
+use item::{Category, Item};
+use postprocess;
+
+pub struct GenOptions<'a> {
+    pub filter: FilterOptions,
+    pub rust_enums: bool,
+    pub newtype_enum: ::regex_set::RegexSet,
+    pub bitfield_enum: ::regex_set::RegexSet,
+    pub override_enum_ty: String,
+    pub merge_extern_blocks: bool,
+    pub sort_semantically: bool,
+    /// User-registered `ParseCallbacks`, consulted for renaming and extra
+    /// derives as each item is generated. `None` unless a library caller
+    /// registered one with `Builder::parse_callbacks` -- there's no CLI
+    /// flag for it.
+    pub callbacks: Option<&'a dyn ParseCallbacks>,
+}
+
+/// Applies `item_name`/`enum_variant_name` to a copy of `g`, so downstream
+/// codegen can stay oblivious to whether callbacks are registered.
+fn renamed(g: &Global, callbacks: Option<&dyn ParseCallbacks>) -> Global {
+    let mut out = g.clone();
+    let cb = match callbacks {
+        Some(cb) => cb,
+        None => return out,
+    };
+    let original_name = g.name.clone();
+    if let Some(name) = cb.item_name(&original_name) {
+        out.name = name;
+    }
+    for v in &mut out.variants {
+        if let Some(name) = cb.enum_variant_name(&original_name, &v.name, v.value) {
+            v.name = name;
+        }
+    }
+    out
+}
+
+fn params_decl(g: &Global) -> String {
+    g.params
+        .iter()
+        .map(|p| format!("{}: {}", p.name, p.ty))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn function_item(g: &Global) -> Item {
+    let ret = if g.ret_ty.is_empty() || g.ret_ty == "void" {
+        String::new()
+    } else {
+        format!(" -> {}", g.ret_ty)
+    };
+    Item {
+        category: Category::Function,
+        name: g.name.clone(),
+        code: format!("pub fn {}({}){};", g.name, params_decl(g), ret),
+        abi: Some("C".to_string()),
+    }
+}
+
+fn var_item(g: &Global) -> Item {
+    Item {
+        category: Category::Static,
+        name: g.name.clone(),
+        code: format!("extern \"C\" {{ pub static mut {}: {}; }}", g.name, g.ret_ty),
+        abi: None,
+    }
+}
+
+fn enum_item(g: &Global, opts: &GenOptions) -> Item {
+    let repr_ty = if opts.override_enum_ty.is_empty() {
+        "u32"
+    } else {
+        &opts.override_enum_ty
+    };
+    let style = enums::choose_style(
+        &g.name,
+        opts.rust_enums,
+        &opts.newtype_enum,
+        &opts.bitfield_enum,
+    );
+    let category = if style == enums::EnumStyle::Constants {
+        Category::Const
+    } else {
+        Category::Type
+    };
+    let extra_derives = opts
+        .callbacks
+        .map(|cb| cb.add_derives(&DeriveInfo { name: &g.name }))
+        .unwrap_or_default();
+    Item {
+        category,
+        name: g.name.clone(),
+        code: enums::emit(g, style, repr_ty, &extra_derives),
+        abi: None,
+    }
+}
+
+fn opaque_type_item(g: &Global) -> Item {
+    Item {
+        category: Category::Type,
+        name: g.name.clone(),
+        code: format!(
+            "#[repr(C)]\npub struct {} {{\n    _opaque: [u8; 0],\n}}\n",
+            g.name
+        ),
+        abi: None,
+    }
+}
+
+/// Runs filtering, per-kind codegen and the optional post-processing pass,
+/// returning the final flattened item list in print order.
+pub fn generate_items<'a>(globals: &[Global], opts: &GenOptions<'a>) -> Vec<Item> {
+    let filtered = filter::filter_globals(globals, &opts.filter);
+
+    let mut items = Vec::with_capacity(filtered.len());
+    for (g, disposition) in filtered {
+        // `static`/`static inline` functions have no linkable symbol, so
+        // they're dropped here entirely -- `--wrap-static-fns` is the only
+        // way to get a binding for them, via a generated wrapper decl.
+        if g.kind == ItemKind::Function && g.is_static_inline {
+            continue;
+        }
+        let g = renamed(&g, opts.callbacks);
+        let item = match g.kind {
+            ItemKind::Function => function_item(&g),
+            ItemKind::Var => var_item(&g),
+            ItemKind::Enum if disposition == Disposition::Keep => enum_item(&g, opts),
+            ItemKind::Struct | ItemKind::Enum => opaque_type_item(&g),
+        };
+        items.push(item);
+    }
+
+    if opts.merge_extern_blocks {
+        items = postprocess::merge_extern_blocks(items);
+    }
+    if opts.sort_semantically {
+        items = postprocess::sort_semantically(items);
+    }
+    items
+}
+
+/// Renders the final item list as Rust source text, wrapping `Function`
+/// items in an `extern "<abi>"` block.
+pub fn print_items(items: &[Item]) -> String {
+    let mut out = String::new();
+    for item in items {
+        match item.abi {
+            Some(ref abi) => {
+                out.push_str(&format!("extern \"{}\" {{\n", abi));
+                for line in item.code.lines() {
+                    out.push_str("    ");
+                    out.push_str(line);
+                    out.push('\n');
+                }
+                out.push_str("}\n");
+            }
+            None => {
+                out.push_str(&item.code);
+                if !item.code.ends_with('\n') {
+                    out.push('\n');
+                }
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ir::{Global, ItemKind};
+
+    fn default_opts() -> GenOptions<'static> {
+        GenOptions {
+            filter: FilterOptions::default(),
+            rust_enums: true,
+            newtype_enum: ::regex_set::RegexSet::default(),
+            bitfield_enum: ::regex_set::RegexSet::default(),
+            override_enum_ty: String::new(),
+            merge_extern_blocks: false,
+            sort_semantically: false,
+            callbacks: None,
+        }
+    }
+
+    #[test]
+    fn functions_default_to_their_own_extern_block() {
+        let globals = vec![
+            Global::new(ItemKind::Function, "a", "t.h"),
+            Global::new(ItemKind::Function, "b", "t.h"),
+        ];
+        let items = generate_items(&globals, &default_opts());
+        let printed = print_items(&items);
+        assert_eq!(printed.matches("extern \"C\" {").count(), 2);
+    }
+
+    #[test]
+    fn blocklisted_function_is_dropped_entirely() {
+        let globals = vec![
+            Global::new(ItemKind::Function, "a", "t.h"),
+            Global::new(ItemKind::Function, "b", "t.h"),
+        ];
+        let opts = GenOptions {
+            filter: FilterOptions {
+                blocklist_functions: ::regex_set::RegexSet::new(["b"]).unwrap(),
+                ..FilterOptions::default()
+            },
+            ..default_opts()
+        };
+        let items = generate_items(&globals, &opts);
+        assert_eq!(items.len(), 1);
+    }
+
+    #[test]
+    fn enums_are_rendered_as_rust_enums_by_default() {
+        let mut g = Global::new(ItemKind::Enum, "Color", "t.h");
+        g.variants = vec![::ir::EnumVariant { name: "RED".into(), value: 0 }];
+        let items = generate_items(&[g], &default_opts());
+        assert!(items[0].code.contains("pub enum Color"));
+    }
+
+    #[test]
+    fn merge_extern_blocks_combines_adjacent_functions() {
+        let globals = vec![
+            Global::new(ItemKind::Function, "a", "t.h"),
+            Global::new(ItemKind::Function, "b", "t.h"),
+        ];
+        let opts = GenOptions {
+            merge_extern_blocks: true,
+            ..default_opts()
+        };
+        let items = generate_items(&globals, &opts);
+        let printed = print_items(&items);
+        assert_eq!(printed.matches("extern \"C\" {").count(), 1);
+    }
+
+    #[test]
+    fn static_inline_functions_are_excluded_from_normal_codegen() {
+        let mut g = Global::new(ItemKind::Function, "helper", "t.h");
+        g.is_static_inline = true;
+        let items = generate_items(&[g], &default_opts());
+        assert!(items.is_empty());
+    }
+
+    #[derive(Debug)]
+    struct StripPrefix;
+
+    impl ParseCallbacks for StripPrefix {
+        fn item_name(&self, original_name: &str) -> Option<String> {
+            original_name.strip_prefix("lib_").map(|s| s.to_string())
+        }
+
+        fn enum_variant_name(&self, _enum_name: &str, variant_name: &str, _value: i64) -> Option<String> {
+            variant_name.strip_prefix("COLOR_").map(|s| s.to_string())
+        }
+
+        fn add_derives(&self, _info: &DeriveInfo) -> Vec<String> {
+            vec!["Hash".to_string()]
+        }
+    }
+
+    #[test]
+    fn callbacks_rename_items_and_variants_and_add_derives() {
+        let cb = StripPrefix;
+        let mut g = Global::new(ItemKind::Function, "lib_frobnicate", "t.h");
+        let opts = GenOptions {
+            callbacks: Some(&cb as &dyn ParseCallbacks),
+            ..default_opts()
+        };
+        let items = generate_items(&[g.clone()], &opts);
+        assert!(items[0].code.contains("pub fn frobnicate("));
+
+        g = Global::new(ItemKind::Enum, "lib_Color", "t.h");
+        g.variants = vec![::ir::EnumVariant { name: "COLOR_RED".into(), value: 0 }];
+        let items = generate_items(&[g], &opts);
+        assert!(items[0].code.contains("pub enum Color"));
+        assert!(items[0].code.contains("RED = 0"));
+        assert!(items[0].code.contains("derive(Debug, Copy, Clone, PartialEq, Eq, Hash)"));
+    }
+}
@@ -0,0 +1,65 @@
+//! The intermediate representation produced by the parser and consumed by
+//! the filtering pass and codegen.
+
+/// The different top-level things a header can define.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemKind {
+    Function,
+    Var,
+    Struct,
+    Enum,
+}
+
+/// A single enum variant, in declaration order.
+#[derive(Debug, Clone)]
+pub struct EnumVariant {
+    pub name: String,
+    pub value: i64,
+}
+
+/// A function parameter.
+#[derive(Debug, Clone)]
+pub struct Param {
+    pub name: String,
+    pub ty: String,
+}
+
+/// One top-level item parsed out of a header, analogous to the old
+/// `Global` enum: every kind of item bindgen can emit a binding for.
+#[derive(Debug, Clone)]
+pub struct Global {
+    pub kind: ItemKind,
+    /// The name as it appears in C.
+    pub name: String,
+    /// The path of the file this item was found in, used by
+    /// `--blocklist-file` and `--depfile`.
+    pub file: String,
+    /// True for functions declared `static` or `static inline`, which have
+    /// no linkable symbol.
+    pub is_static_inline: bool,
+    /// Return type spelling, for functions.
+    pub ret_ty: String,
+    /// Parameters, for functions.
+    pub params: Vec<Param>,
+    /// Names of other items this one references (e.g. a function's
+    /// parameter and return types), used to transitively pull in types
+    /// reachable from allowlisted items.
+    pub referenced: Vec<String>,
+    /// Variants, for enums.
+    pub variants: Vec<EnumVariant>,
+}
+
+impl Global {
+    pub fn new(kind: ItemKind, name: &str, file: &str) -> Global {
+        Global {
+            kind,
+            name: name.to_owned(),
+            file: file.to_owned(),
+            is_static_inline: false,
+            ret_ty: String::new(),
+            params: Vec::new(),
+            referenced: Vec::new(),
+            variants: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,22 @@
+//! The flattened, almost-ready-to-print representation codegen builds up
+//! before the optional post-processing pass and final printing.
+
+/// The category an item falls into for `--sort-semantically`'s ordering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Category {
+    Type,
+    Const,
+    Function,
+    Static,
+}
+
+/// One top-level Rust item about to be printed.
+#[derive(Debug, Clone)]
+pub struct Item {
+    pub category: Category,
+    pub name: String,
+    pub code: String,
+    /// Set for `Function` items: the ABI string of the `extern` block the
+    /// declaration belongs in (e.g. `"C"`).
+    pub abi: Option<String>,
+}
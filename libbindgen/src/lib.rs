@@ -0,0 +1,351 @@
+//! Generates Rust FFI bindings to C libraries.
+//!
+//! The `Builder` gathers options (mirroring the CLI's flags) and
+//! `generate()` runs the header scan, the allow/blocklist filtering pass,
+//! and codegen.
+
+mod callbacks;
+mod depfile;
+mod dynamic_loading;
+mod enums;
+mod filter;
+mod gen;
+mod ir;
+mod item;
+mod parser;
+mod postprocess;
+mod regex_set;
+mod wrap_static_fns;
+
+use std::io::{self, Write};
+use std::path::Path;
+
+pub use callbacks::{DeriveInfo, IntKind, ParseCallbacks};
+use filter::FilterOptions;
+use regex_set::RegexSet;
+
+/// How a `--link`ed library should be linked against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkType {
+    Static,
+    Dynamic,
+    Framework,
+}
+
+#[derive(Debug, Clone)]
+struct Link {
+    name: String,
+    kind: LinkType,
+}
+
+/// All the knobs `Builder` accumulates before `generate()` runs.
+///
+/// `generate()` does not read `emit_ast`, `ctypes_prefix`, `use_core`,
+/// `derive_debug`, `convert_macros`, `match_pats`, `remove_prefix`,
+/// `macro_int_types`, `builtins`, `dont_convert_floats` or
+/// `allow_unknown_types`: they are stored and have no effect on the
+/// output (enums always derive `Debug`). `override_enum_ty` is used
+/// verbatim as the enum's Rust representation type, so it takes a Rust
+/// integer type such as `u8`; C type names such as `uchar` are not
+/// translated.
+#[derive(Debug, Default)]
+pub struct BindgenOptions {
+    pub header: String,
+    pub clang_args: Vec<String>,
+    pub emit_ast: bool,
+    pub ctypes_prefix: Vec<String>,
+    pub use_core: bool,
+    pub derive_debug: bool,
+    pub rust_enums: bool,
+    pub override_enum_ty: String,
+    pub convert_macros: bool,
+    pub match_pats: Vec<String>,
+    pub remove_prefix: Option<String>,
+    pub macro_int_types: Vec<String>,
+    pub builtins: bool,
+    pub dont_convert_floats: bool,
+    pub allow_unknown_types: bool,
+    dynamic_library_name: Option<String>,
+    dynamic_link_require_all: bool,
+    pub wrap_static_fns: bool,
+    pub wrap_static_fns_path: String,
+    pub wrap_static_fns_suffix: String,
+    pub merge_extern_blocks: bool,
+    pub sort_semantically: bool,
+    links: Vec<Link>,
+
+    allowlist_function_pats: Vec<String>,
+    allowlist_type_pats: Vec<String>,
+    allowlist_var_pats: Vec<String>,
+    blocklist_type_pats: Vec<String>,
+    blocklist_function_pats: Vec<String>,
+    blocklist_item_pats: Vec<String>,
+    blocklist_file_pats: Vec<String>,
+    newtype_enum_pats: Vec<String>,
+    bitfield_enum_pats: Vec<String>,
+}
+
+/// Accumulates generation options; call `generate()` once configured.
+#[derive(Debug)]
+pub struct Builder<'a> {
+    options: BindgenOptions,
+    callbacks: Option<Box<dyn ParseCallbacks>>,
+    _marker: ::std::marker::PhantomData<&'a ()>,
+}
+
+macro_rules! bool_setter {
+    ($name:ident, $field:ident) => {
+        pub fn $name(&mut self, value: bool) -> &mut Self {
+            self.options.$field = value;
+            self
+        }
+    };
+}
+
+macro_rules! string_setter {
+    ($name:ident, $field:ident) => {
+        pub fn $name(&mut self, value: String) -> &mut Self {
+            self.options.$field = value;
+            self
+        }
+    };
+}
+
+macro_rules! pattern_setter {
+    ($name:ident, $field:ident) => {
+        pub fn $name<S: Into<String>>(&mut self, pattern: S) -> &mut Self {
+            self.options.$field.push(pattern.into());
+            self
+        }
+    };
+}
+
+impl<'a> Builder<'a> {
+    pub fn new<S: Into<String>>(header: S) -> Builder<'static> {
+        let options = BindgenOptions {
+            header: header.into(),
+            rust_enums: true,
+            derive_debug: true,
+            wrap_static_fns_path: "bindgen/extern.c".to_string(),
+            wrap_static_fns_suffix: "__extern".to_string(),
+            ..BindgenOptions::default()
+        };
+        Builder {
+            options,
+            callbacks: None,
+            _marker: ::std::marker::PhantomData,
+        }
+    }
+
+    bool_setter!(emit_ast, emit_ast);
+    bool_setter!(use_core, use_core);
+    bool_setter!(derive_debug, derive_debug);
+    bool_setter!(rust_enums, rust_enums);
+    bool_setter!(convert_macros, convert_macros);
+
+    string_setter!(override_enum_ty, override_enum_ty);
+
+    pattern_setter!(match_pat, match_pats);
+    pattern_setter!(allowlist_function, allowlist_function_pats);
+    pattern_setter!(allowlist_type, allowlist_type_pats);
+    pattern_setter!(allowlist_var, allowlist_var_pats);
+    pattern_setter!(blocklist_type, blocklist_type_pats);
+    pattern_setter!(blocklist_function, blocklist_function_pats);
+    pattern_setter!(blocklist_item, blocklist_item_pats);
+    pattern_setter!(blocklist_file, blocklist_file_pats);
+    pattern_setter!(newtype_enum, newtype_enum_pats);
+    pattern_setter!(bitfield_enum, bitfield_enum_pats);
+
+    pub fn ctypes_prefix(&mut self, prefix: Vec<String>) -> &mut Self {
+        self.options.ctypes_prefix = prefix;
+        self
+    }
+
+    pub fn clang_arg<S: Into<String>>(&mut self, arg: S) -> &mut Self {
+        self.options.clang_args.push(arg.into());
+        self
+    }
+
+    pub fn remove_prefix<S: Into<String>>(&mut self, prefix: S) -> &mut Self {
+        self.options.remove_prefix = Some(prefix.into());
+        self
+    }
+
+    pub fn macro_int_types<'i, I: IntoIterator<Item = &'i str>>(&mut self, types: I) -> &mut Self {
+        self.options.macro_int_types = types.into_iter().map(String::from).collect();
+        self
+    }
+
+    pub fn builtins(&mut self) -> &mut Self {
+        self.options.builtins = true;
+        self
+    }
+
+    pub fn dont_convert_floats(&mut self) -> &mut Self {
+        self.options.dont_convert_floats = true;
+        self
+    }
+
+    pub fn allow_unknown_types(&mut self) -> &mut Self {
+        self.options.allow_unknown_types = true;
+        self
+    }
+
+    /// Instead of linking the bindings against the library directly, emit a
+    /// `dlopen`-based wrapper struct named `name` that resolves each
+    /// allowlisted function at runtime.
+    pub fn dynamic_library_name<S: Into<String>>(&mut self, name: S) -> &mut Self {
+        self.options.dynamic_library_name = Some(name.into());
+        self
+    }
+
+    /// Registers a `ParseCallbacks` implementation, available to library
+    /// users embedding `Builder` directly -- the CLI has no flag for this,
+    /// since a trait implementation can't be named on a command line.
+    pub fn parse_callbacks(&mut self, callbacks: Box<dyn ParseCallbacks>) -> &mut Self {
+        self.callbacks = Some(callbacks);
+        self
+    }
+
+    bool_setter!(dynamic_link_require_all, dynamic_link_require_all);
+
+    bool_setter!(wrap_static_fns, wrap_static_fns);
+    string_setter!(wrap_static_fns_path, wrap_static_fns_path);
+    string_setter!(wrap_static_fns_suffix, wrap_static_fns_suffix);
+
+    bool_setter!(merge_extern_blocks, merge_extern_blocks);
+    bool_setter!(sort_semantically, sort_semantically);
+
+    pub fn link<S: Into<String>>(&mut self, name: S, kind: LinkType) -> &mut Self {
+        let name = name.into();
+        self.options.links.push(Link { name, kind });
+        self
+    }
+
+    fn include_dirs(&self) -> Vec<String> {
+        self.options
+            .clang_args
+            .iter()
+            .filter_map(|a| a.strip_prefix("-I").map(String::from))
+            .collect()
+    }
+
+    fn filter_options(&self) -> FilterOptions {
+        FilterOptions {
+            allowlist_functions: RegexSet::new(&self.options.allowlist_function_pats).unwrap(),
+            allowlist_types: RegexSet::new(&self.options.allowlist_type_pats).unwrap(),
+            allowlist_vars: RegexSet::new(&self.options.allowlist_var_pats).unwrap(),
+            blocklist_types: RegexSet::new(&self.options.blocklist_type_pats).unwrap(),
+            blocklist_functions: RegexSet::new(&self.options.blocklist_function_pats).unwrap(),
+            blocklist_items: RegexSet::new(&self.options.blocklist_item_pats).unwrap(),
+            blocklist_files: RegexSet::new(&self.options.blocklist_file_pats).unwrap(),
+        }
+    }
+
+    /// Parses the header, filters the resulting items, runs codegen and
+    /// returns the finished bindings.
+    #[allow(clippy::result_unit_err)]
+    pub fn generate(&self) -> Result<Bindings, ()> {
+        let parsed = parser::parse(Path::new(&self.options.header), &self.include_dirs())
+            .map_err(|_| ())?;
+
+        if let Some(ref struct_name) = self.options.dynamic_library_name {
+            let filter = self.filter_options();
+            let functions: Vec<ir::Global> = filter::filter_globals(&parsed.globals, &filter)
+                .into_iter()
+                .filter(|(g, d)| g.kind == ir::ItemKind::Function && *d == filter::Disposition::Keep)
+                .map(|(g, _)| g)
+                .collect();
+            let text = dynamic_loading::emit(
+                struct_name,
+                &functions,
+                self.options.dynamic_link_require_all,
+            );
+            return Ok(Bindings { text, files: parsed.files });
+        }
+
+        let gen_opts = gen::GenOptions {
+            filter: self.filter_options(),
+            rust_enums: self.options.rust_enums,
+            newtype_enum: RegexSet::new(&self.options.newtype_enum_pats).unwrap(),
+            bitfield_enum: RegexSet::new(&self.options.bitfield_enum_pats).unwrap(),
+            override_enum_ty: self.options.override_enum_ty.clone(),
+            merge_extern_blocks: self.options.merge_extern_blocks,
+            sort_semantically: self.options.sort_semantically,
+            callbacks: self.callbacks.as_deref(),
+        };
+        let mut items = gen::generate_items(&parsed.globals, &gen_opts);
+
+        if self.options.wrap_static_fns {
+            let filtered_functions: Vec<ir::Global> =
+                filter::filter_globals(&parsed.globals, &self.filter_options())
+                    .into_iter()
+                    .filter(|(g, d)| g.kind == ir::ItemKind::Function && *d == filter::Disposition::Keep)
+                    .map(|(g, _)| g)
+                    .collect();
+            let decls = wrap_static_fns::wrap_all_to_file(
+                &filtered_functions,
+                &self.options.wrap_static_fns_suffix,
+                &self.options.wrap_static_fns_path,
+            )
+            .map_err(|_| ())?;
+            for decl in decls {
+                items.push(item::Item {
+                    category: item::Category::Function,
+                    name: decl.clone(),
+                    code: decl,
+                    abi: Some("C".to_string()),
+                });
+            }
+            if self.options.merge_extern_blocks {
+                items = postprocess::merge_extern_blocks(items);
+            }
+            if self.options.sort_semantically {
+                items = postprocess::sort_semantically(items);
+            }
+        }
+
+        Ok(Bindings {
+            text: self.link_attrs() + &gen::print_items(&items),
+            files: parsed.files,
+        })
+    }
+
+    /// Renders a `#[link(name = "...", kind = "...")]` attribute on an
+    /// empty `extern "C"` block per `--link`ed library, so the linker
+    /// actually pulls each one in.
+    fn link_attrs(&self) -> String {
+        let mut out = String::new();
+        for link in &self.options.links {
+            let kind = match link.kind {
+                LinkType::Static => "static",
+                LinkType::Dynamic => "dylib",
+                LinkType::Framework => "framework",
+            };
+            out.push_str(&format!(
+                "#[link(name = \"{}\", kind = \"{}\")]\nextern \"C\" {{}}\n",
+                link.name, kind
+            ));
+        }
+        out
+    }
+}
+
+/// The finished, ready-to-write bindings.
+pub struct Bindings {
+    text: String,
+    files: Vec<String>,
+}
+
+impl Bindings {
+    pub fn write(&self, mut dest: Box<dyn Write>) -> io::Result<()> {
+        dest.write_all(self.text.as_bytes())
+    }
+
+    /// Writes a `make`-format depfile listing every header transitively
+    /// included while parsing, so build systems re-run bindgen when any of
+    /// them changes.
+    pub fn write_depfile<P: AsRef<Path>>(&self, depfile_path: P, output_path: &str) -> io::Result<()> {
+        depfile::write(depfile_path, output_path, &self.files)
+    }
+}
@@ -0,0 +1,408 @@
+//! A small, self-contained header scanner standing in for the real
+//! clang-based parser: it follows `#include`s and recognizes simple
+//! function, struct, enum and variable declarations to build the `Global`
+//! list that filtering and codegen operate on.
+//!
+//! It does not aim to understand arbitrary C -- no macros, no nested
+//! `#if`, no function declarations split across lines (only enum and
+//! struct bodies may span lines) -- just enough structure for the
+//! features built on top of it to have real data to work with.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use ir::{EnumVariant, Global, ItemKind, Param};
+
+/// Everything the parse of a translation unit produced.
+#[derive(Debug, Default)]
+pub struct ParseResult {
+    pub globals: Vec<Global>,
+    /// Every distinct file seen while parsing, in the order first visited.
+    pub files: Vec<String>,
+}
+
+pub fn parse<P: AsRef<Path>>(entry: P, include_dirs: &[String]) -> io::Result<ParseResult> {
+    let mut result = ParseResult::default();
+    let mut visited = HashSet::new();
+    parse_file(entry.as_ref(), include_dirs, &mut visited, &mut result)?;
+    Ok(result)
+}
+
+fn parse_file(
+    path: &Path,
+    include_dirs: &[String],
+    visited: &mut HashSet<PathBuf>,
+    result: &mut ParseResult,
+) -> io::Result<()> {
+    let canonical = path.to_path_buf();
+    if !visited.insert(canonical.clone()) {
+        return Ok(());
+    }
+    let path_str = path.to_string_lossy().into_owned();
+    result.files.push(path_str.clone());
+
+    let contents = fs::read_to_string(path)?;
+    let dir = path.parent().unwrap_or_else(|| Path::new(""));
+
+    let mut lines = contents.lines().peekable();
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+
+        if let Some(included) = parse_include(trimmed) {
+            if let Some(resolved) = resolve_include(&included, dir, include_dirs) {
+                parse_file(&resolved, include_dirs, visited, result)?;
+            }
+            continue;
+        }
+
+        if let Some(g) = parse_enum(trimmed, &mut lines, &path_str) {
+            result.globals.push(g);
+            continue;
+        }
+
+        if let Some(g) = parse_function(trimmed, &path_str) {
+            result.globals.push(g);
+            continue;
+        }
+
+        if let Some(g) = parse_struct(trimmed, &mut lines, &path_str) {
+            result.globals.push(g);
+            continue;
+        }
+
+        if let Some(g) = parse_extern_var(trimmed, &path_str) {
+            result.globals.push(g);
+            continue;
+        }
+    }
+
+    Ok(())
+}
+
+struct Include {
+    name: String,
+    quoted: bool,
+}
+
+fn parse_include(line: &str) -> Option<Include> {
+    let rest = line.strip_prefix("#include")?.trim();
+    if let Some(quoted) = rest.strip_prefix('"') {
+        let end = quoted.find('"')?;
+        Some(Include { name: quoted[..end].to_string(), quoted: true })
+    } else if let Some(angled) = rest.strip_prefix('<') {
+        let end = angled.find('>')?;
+        Some(Include { name: angled[..end].to_string(), quoted: false })
+    } else {
+        None
+    }
+}
+
+fn resolve_include(include: &Include, dir: &Path, include_dirs: &[String]) -> Option<PathBuf> {
+    if include.quoted {
+        let candidate = dir.join(&include.name);
+        if candidate.exists() {
+            return Some(candidate);
+        }
+    }
+    for idir in include_dirs {
+        let candidate = Path::new(idir).join(&include.name);
+        if candidate.exists() {
+            return Some(candidate);
+        }
+    }
+    None
+}
+
+/// `enum Name { A, B = 2, C };` -- unspecified variants continue from the
+/// previous explicit value, exactly like C.
+fn parse_enum<'a, I: Iterator<Item = &'a str>>(
+    line: &str,
+    rest: &mut ::std::iter::Peekable<I>,
+    file: &str,
+) -> Option<Global> {
+    let after = line.strip_prefix("enum")?.trim();
+    let open = after.find('{')?;
+    let name = after[..open].trim().to_string();
+    if name.is_empty() {
+        return None;
+    }
+
+    let mut body = after[open + 1..].to_string();
+    while !body.contains('}') {
+        match rest.next() {
+            Some(l) => {
+                body.push('\n');
+                body.push_str(l);
+            }
+            None => break,
+        }
+    }
+    let body = body.split('}').next().unwrap_or("");
+
+    let mut g = Global::new(ItemKind::Enum, &name, file);
+    let mut next_value: i64 = 0;
+    for part in body.split(',') {
+        let part = part.trim();
+        if part.is_empty() {
+            continue;
+        }
+        let (variant_name, value) = if let Some(eq) = part.find('=') {
+            let value = parse_discriminant(&part[eq + 1..])?;
+            (part[..eq].trim().to_string(), value)
+        } else {
+            (part.to_string(), next_value)
+        };
+        next_value = value + 1;
+        g.variants.push(EnumVariant { name: variant_name, value });
+    }
+    Some(g)
+}
+
+/// Evaluates an enum discriminant: a decimal or hex integer literal,
+/// optionally negated or left-shifted (`1 << 4`) -- the forms bitfield and
+/// flag enums actually use. Returns `None` rather than guessing if the
+/// expression isn't one of these, so a value like a char literal drops the
+/// whole enum instead of silently fabricating a wrong discriminant.
+fn parse_discriminant(s: &str) -> Option<i64> {
+    let s = s.trim();
+    match s.find("<<") {
+        Some(pos) => {
+            let lhs = parse_int_literal(s[..pos].trim())?;
+            let rhs = parse_int_literal(s[pos + 2..].trim())?;
+            Some(lhs << rhs)
+        }
+        None => parse_int_literal(s),
+    }
+}
+
+fn parse_int_literal(s: &str) -> Option<i64> {
+    let (negative, s) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest.trim()),
+        None => (false, s),
+    };
+    let value = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => i64::from_str_radix(hex, 16).ok()?,
+        None => s.parse::<i64>().ok()?,
+    };
+    Some(if negative { -value } else { value })
+}
+
+/// `struct Name { field-decls... };` -- field types are recorded as
+/// `referenced`, so a struct pulls in other structs it's built from.
+fn parse_struct<'a, I: Iterator<Item = &'a str>>(
+    line: &str,
+    rest: &mut ::std::iter::Peekable<I>,
+    file: &str,
+) -> Option<Global> {
+    let after = line.strip_prefix("struct")?.trim();
+    let open = after.find('{')?;
+    let name = after[..open].trim().to_string();
+    if name.is_empty() {
+        return None;
+    }
+
+    let mut body = after[open + 1..].to_string();
+    while !body.contains('}') {
+        match rest.next() {
+            Some(l) => {
+                body.push('\n');
+                body.push_str(l);
+            }
+            None => break,
+        }
+    }
+    let body = body.split('}').next().unwrap_or("");
+
+    let mut g = Global::new(ItemKind::Struct, &name, file);
+    for field in body.split(';') {
+        let field = field.trim();
+        if field.is_empty() {
+            continue;
+        }
+        if let Some(idx) = field.rfind([' ', '*']) {
+            g.referenced.push(base_type_name(&field[..idx]));
+        }
+    }
+    Some(g)
+}
+
+/// `extern TYPE name;`
+fn parse_extern_var(line: &str, file: &str) -> Option<Global> {
+    let line = line.trim_end();
+    let after = line.strip_prefix("extern")?.trim();
+    let after = after.strip_suffix(';')?.trim();
+    if after.contains('(') {
+        return None;
+    }
+    let idx = after.rfind([' ', '*'])?;
+    let name = after[idx + 1..].trim().to_string();
+    let ty = after[..idx].trim().to_string();
+    if name.is_empty() || ty.is_empty() {
+        return None;
+    }
+    let mut g = Global::new(ItemKind::Var, &name, file);
+    g.ret_ty = ty.clone();
+    g.referenced.push(base_type_name(&ty));
+    Some(g)
+}
+
+fn parse_function(line: &str, file: &str) -> Option<Global> {
+    let line = line.trim_end();
+    if !line.ends_with(';') {
+        return None;
+    }
+    let line = &line[..line.len() - 1];
+    let open = line.find('(')?;
+    if !line.trim_end().ends_with(')') {
+        return None;
+    }
+    let head = line[..open].trim();
+    let params_str = &line[open + 1..line.rfind(')')?];
+
+    let mut is_static_inline = false;
+    let mut head = head.to_string();
+    for kw in &["static inline", "inline static", "static", "inline"] {
+        if head.starts_with(kw) {
+            is_static_inline = true;
+            head = head[kw.len()..].trim().to_string();
+        }
+    }
+
+    let last_space = head.rfind([' ', '*'])?;
+    let name = head[last_space + 1..].trim().to_string();
+    if name.is_empty() || name.contains(|c: char| !(c.is_alphanumeric() || c == '_')) {
+        return None;
+    }
+    let ret_ty = head[..=last_space].trim().to_string();
+
+    let mut params = Vec::new();
+    let mut referenced = Vec::new();
+    let trimmed_params = params_str.trim();
+    if !trimmed_params.is_empty() && trimmed_params != "void" {
+        for raw in trimmed_params.split(',') {
+            let raw = raw.trim();
+            let split_at = raw.rfind([' ', '*']);
+            let (ty, pname) = match split_at {
+                Some(idx) => (raw[..=idx].trim().to_string(), raw[idx + 1..].trim().to_string()),
+                None => (raw.to_string(), String::new()),
+            };
+            referenced.push(base_type_name(&ty));
+            params.push(Param { name: pname, ty });
+        }
+    }
+    referenced.push(base_type_name(&ret_ty));
+
+    let mut g = Global::new(ItemKind::Function, &name, file);
+    g.is_static_inline = is_static_inline;
+    g.ret_ty = ret_ty;
+    g.params = params;
+    g.referenced = referenced.into_iter().filter(|s| !s.is_empty()).collect();
+    Some(g)
+}
+
+fn base_type_name(ty: &str) -> String {
+    let mut ty = ty.trim_matches(|c: char| c == '*' || c.is_whitespace());
+    for kw in &["const ", "struct ", "enum "] {
+        if let Some(rest) = ty.strip_prefix(kw) {
+            ty = rest.trim_start();
+        }
+    }
+    ty.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn write_temp(name: &str, contents: &str) -> PathBuf {
+        let mut path = ::std::env::temp_dir();
+        path.push(format!("bindgen_parser_test_{}_{}", ::std::process::id(), name));
+        let mut f = fs::File::create(&path).unwrap();
+        f.write_all(contents.as_bytes()).unwrap();
+        path
+    }
+
+    #[test]
+    fn parses_simple_function() {
+        let path = write_temp("fn.h", "int add(int a, int b);\n");
+        let result = parse(&path, &[]).unwrap();
+        assert_eq!(result.globals.len(), 1);
+        let g = &result.globals[0];
+        assert_eq!(g.name, "add");
+        assert_eq!(g.ret_ty, "int");
+        assert_eq!(g.params.len(), 2);
+        assert!(!g.is_static_inline);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn detects_static_inline() {
+        let path = write_temp("inline.h", "static inline int sq(int x) { return x * x; }\nstatic inline int sq2(int x);\n");
+        let result = parse(&path, &[]).unwrap();
+        // The definition line doesn't end in `;` so only the second (a
+        // plain declaration) is picked up by this scanner.
+        assert_eq!(result.globals.len(), 1);
+        assert!(result.globals[0].is_static_inline);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn parses_struct_fields_as_referenced_types() {
+        let path = write_temp("struct.h", "struct Point { Inner x; int y; };\n");
+        let result = parse(&path, &[]).unwrap();
+        assert_eq!(result.globals.len(), 1);
+        let g = &result.globals[0];
+        assert_eq!(g.name, "Point");
+        assert!(g.referenced.iter().any(|r| r == "Inner"));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn parses_enum_variants_with_explicit_and_implicit_values() {
+        let path = write_temp("enum.h", "enum Color { RED, GREEN = 5, BLUE };\n");
+        let result = parse(&path, &[]).unwrap();
+        assert_eq!(result.globals.len(), 1);
+        let g = &result.globals[0];
+        assert_eq!(g.name, "Color");
+        assert_eq!(g.variants.len(), 3);
+        assert_eq!(g.variants[0].value, 0);
+        assert_eq!(g.variants[1].value, 5);
+        assert_eq!(g.variants[2].value, 6);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn parses_hex_and_shift_enum_discriminants() {
+        let path = write_temp("bitfield_enum.h", "enum Flags { A = 0x1, B = 0x2, C = 1 << 2 };\n");
+        let result = parse(&path, &[]).unwrap();
+        assert_eq!(result.globals.len(), 1);
+        let g = &result.globals[0];
+        assert_eq!(g.variants[0].value, 1);
+        assert_eq!(g.variants[1].value, 2);
+        assert_eq!(g.variants[2].value, 4);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn unparseable_enum_discriminant_drops_the_whole_enum() {
+        let path = write_temp("char_enum.h", "enum Bad { A = 'x' };\n");
+        let result = parse(&path, &[]).unwrap();
+        assert!(result.globals.is_empty());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn follows_quoted_includes_and_collects_files() {
+        let included = write_temp("included.h", "int helper(void);\n");
+        let included_name = included.file_name().unwrap().to_str().unwrap().to_string();
+        let main = write_temp("main.h", &format!("#include \"{}\"\nint main_fn(void);\n", included_name));
+        let result = parse(&main, &[]).unwrap();
+        assert_eq!(result.files.len(), 2);
+        assert_eq!(result.globals.len(), 2);
+        fs::remove_file(&main).unwrap();
+        fs::remove_file(&included).unwrap();
+    }
+}
@@ -0,0 +1,85 @@
+//! Optional passes over the generated item list, run after codegen but
+//! before printing. Both are no-ops unless their flag is set, so existing
+//! output is unchanged by default.
+
+use item::{Category, Item};
+
+/// Coalesces consecutive `extern "ABI" { ... }` items (i.e. `Function`
+/// items) that share the same ABI into a single merged item.
+pub fn merge_extern_blocks(items: Vec<Item>) -> Vec<Item> {
+    let mut out: Vec<Item> = Vec::with_capacity(items.len());
+    for item in items {
+        let mergeable = item.category == Category::Function && item.abi.is_some();
+        if mergeable {
+            if let Some(last) = out.last_mut() {
+                if last.category == Category::Function && last.abi == item.abi {
+                    last.code.push('\n');
+                    last.code.push_str(&item.code);
+                    continue;
+                }
+            }
+        }
+        out.push(item);
+    }
+    out
+}
+
+/// Reorders items into a stable category order (types, then constants,
+/// then functions, then statics), alphabetically within each category, so
+/// diffs between regenerations are stable.
+pub fn sort_semantically(mut items: Vec<Item>) -> Vec<Item> {
+    items.sort_by(|a, b| a.category.cmp(&b.category).then_with(|| a.name.cmp(&b.name)));
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn func(name: &str, abi: &str, code: &str) -> Item {
+        Item {
+            category: Category::Function,
+            name: name.to_string(),
+            code: code.to_string(),
+            abi: Some(abi.to_string()),
+        }
+    }
+
+    fn ty(name: &str) -> Item {
+        Item {
+            category: Category::Type,
+            name: name.to_string(),
+            code: format!("struct {};", name),
+            abi: None,
+        }
+    }
+
+    #[test]
+    fn merges_adjacent_same_abi_functions() {
+        let items = vec![
+            func("a", "C", "fn a();"),
+            func("b", "C", "fn b();"),
+            ty("Point"),
+            func("c", "C", "fn c();"),
+        ];
+        let merged = merge_extern_blocks(items);
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged[0].code, "fn a();\nfn b();");
+        assert_eq!(merged[2].code, "fn c();");
+    }
+
+    #[test]
+    fn does_not_merge_across_differing_abi() {
+        let items = vec![func("a", "C", "fn a();"), func("b", "stdcall", "fn b();")];
+        let merged = merge_extern_blocks(items);
+        assert_eq!(merged.len(), 2);
+    }
+
+    #[test]
+    fn sorts_by_category_then_name() {
+        let items = vec![func("zzz", "C", "fn zzz();"), ty("Beta"), ty("Alpha")];
+        let sorted = sort_semantically(items);
+        let names: Vec<_> = sorted.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, vec!["Alpha", "Beta", "zzz"]);
+    }
+}
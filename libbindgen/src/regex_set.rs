@@ -0,0 +1,201 @@
+//! A small, dependency-free regular expression matcher covering the subset
+//! of syntax `--allowlist-*`/`--blocklist-*` patterns need: literals, `.`,
+//! `*`, `+`, `?`, and `[...]` character classes, matched against the whole
+//! candidate string (as if the pattern were implicitly anchored with `^`
+//! and `$`).
+
+#[derive(Debug, Clone)]
+enum Tok {
+    Char(char),
+    Any,
+    Class(Vec<(char, char)>, bool /* negated */),
+}
+
+#[derive(Debug, Clone)]
+struct Piece {
+    tok: Tok,
+    min: usize,
+    max: usize, // usize::MAX means unbounded
+}
+
+#[derive(Debug, Clone)]
+pub struct Regex {
+    pieces: Vec<Piece>,
+}
+
+#[derive(Debug)]
+pub struct Error(pub String);
+
+impl ::std::fmt::Display for Error {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "invalid pattern: {}", self.0)
+    }
+}
+
+impl Regex {
+    pub fn new(pattern: &str) -> Result<Regex, Error> {
+        let chars: Vec<char> = pattern.chars().collect();
+        let mut pieces = Vec::new();
+        let mut i = 0;
+        while i < chars.len() {
+            let (tok, next) = match chars[i] {
+                '.' => (Tok::Any, i + 1),
+                '[' => {
+                    let close = chars[i..]
+                        .iter()
+                        .position(|&c| c == ']')
+                        .map(|p| p + i)
+                        .ok_or_else(|| Error(format!("unterminated class in `{}`", pattern)))?;
+                    let mut body = &chars[i + 1..close];
+                    let negated = !body.is_empty() && body[0] == '^';
+                    if negated {
+                        body = &body[1..];
+                    }
+                    let mut ranges = Vec::new();
+                    let mut j = 0;
+                    while j < body.len() {
+                        if j + 2 < body.len() && body[j + 1] == '-' {
+                            ranges.push((body[j], body[j + 2]));
+                            j += 3;
+                        } else {
+                            ranges.push((body[j], body[j]));
+                            j += 1;
+                        }
+                    }
+                    (Tok::Class(ranges, negated), close + 1)
+                }
+                c => (Tok::Char(c), i + 1),
+            };
+            i = next;
+            let (min, max) = if i < chars.len() {
+                match chars[i] {
+                    '*' => {
+                        i += 1;
+                        (0, usize::MAX)
+                    }
+                    '+' => {
+                        i += 1;
+                        (1, usize::MAX)
+                    }
+                    '?' => {
+                        i += 1;
+                        (0, 1)
+                    }
+                    _ => (1, 1),
+                }
+            } else {
+                (1, 1)
+            };
+            pieces.push(Piece { tok, min, max });
+        }
+        Ok(Regex { pieces })
+    }
+
+    fn matches_here(tok: &Tok, c: char) -> bool {
+        match *tok {
+            Tok::Char(expected) => expected == c,
+            Tok::Any => true,
+            Tok::Class(ref ranges, negated) => {
+                let hit = ranges.iter().any(|&(lo, hi)| lo <= c && c <= hi);
+                hit != negated
+            }
+        }
+    }
+
+    /// Whether `text` matches this pattern in its entirety.
+    pub fn is_full_match(&self, text: &str) -> bool {
+        let chars: Vec<char> = text.chars().collect();
+        Self::try_match(&self.pieces, 0, &chars, 0)
+    }
+
+    fn try_match(pieces: &[Piece], pi: usize, text: &[char], ti: usize) -> bool {
+        if pi == pieces.len() {
+            return ti == text.len();
+        }
+        let piece = &pieces[pi];
+        // Greedily consume as many repetitions as allowed, then backtrack.
+        let mut consumed = 0;
+        while consumed < piece.max && ti + consumed < text.len()
+            && Self::matches_here(&piece.tok, text[ti + consumed])
+        {
+            consumed += 1;
+        }
+        while consumed + 1 > piece.min {
+            if Self::try_match(pieces, pi + 1, text, ti + consumed) {
+                return true;
+            }
+            if consumed == 0 {
+                break;
+            }
+            consumed -= 1;
+        }
+        consumed == 0 && piece.min == 0 && Self::try_match(pieces, pi + 1, text, ti)
+    }
+}
+
+/// A compiled set of patterns, matched independently against a candidate;
+/// `is_match` reports whether *any* of them fully matches.
+#[derive(Debug, Clone, Default)]
+pub struct RegexSet {
+    regexes: Vec<Regex>,
+}
+
+impl RegexSet {
+    pub fn new<I, S>(patterns: I) -> Result<RegexSet, Error>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut regexes = Vec::new();
+        for pattern in patterns {
+            regexes.push(Regex::new(pattern.as_ref())?);
+        }
+        Ok(RegexSet { regexes })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.regexes.is_empty()
+    }
+
+    pub fn is_match(&self, text: &str) -> bool {
+        self.regexes.iter().any(|r| r.is_full_match(text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn literal_full_match_only() {
+        let set = RegexSet::new(["foo"]).unwrap();
+        assert!(set.is_match("foo"));
+        assert!(!set.is_match("foobar"));
+        assert!(!set.is_match("xfoo"));
+    }
+
+    #[test]
+    fn star_and_classes() {
+        let set = RegexSet::new(["foo_[a-z]*"]).unwrap();
+        assert!(set.is_match("foo_"));
+        assert!(set.is_match("foo_bar"));
+        assert!(!set.is_match("foo_Bar"));
+        assert!(!set.is_match("foo_bar1"));
+    }
+
+    #[test]
+    fn plus_and_dot() {
+        let set = RegexSet::new(["a.+z"]).unwrap();
+        assert!(set.is_match("abz"));
+        assert!(set.is_match("abcz"));
+        assert!(!set.is_match("az"));
+    }
+
+    #[test]
+    fn multiple_patterns_are_ored() {
+        let set = RegexSet::new(["foo", "bar"]).unwrap();
+        assert!(set.is_match("foo"));
+        assert!(set.is_match("bar"));
+        assert!(!set.is_match("baz"));
+    }
+}
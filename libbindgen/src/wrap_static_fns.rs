@@ -0,0 +1,132 @@
+//! `static`/`static inline` functions have no linkable symbol, so bindgen
+//! can't bind to them directly. This generates a non-inline C wrapper per
+//! such function (which *does* have a symbol) and a matching Rust
+//! `extern "C"` declaration pointing at the wrapper.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use ir::Global;
+
+/// One wrapped function: the C source for its wrapper, and the Rust FFI
+/// declaration that binds to it.
+pub struct Wrapper {
+    pub c_source: String,
+    pub rust_decl: String,
+}
+
+fn params_decl(g: &Global) -> String {
+    g.params
+        .iter()
+        .map(|p| format!("{} {}", p.ty, p.name))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn args(g: &Global) -> String {
+    g.params
+        .iter()
+        .map(|p| p.name.clone())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn rust_params_decl(g: &Global) -> String {
+    g.params
+        .iter()
+        .map(|p| format!("{}: {}", p.name, p.ty))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Builds the wrapper for a single `static`/`static inline` function.
+pub fn wrap(g: &Global, suffix: &str) -> Wrapper {
+    let wrapper_name = format!("{}{}", g.name, suffix);
+    let is_void = g.ret_ty.is_empty() || g.ret_ty == "void";
+
+    let mut c_source = String::new();
+    if is_void {
+        c_source.push_str(&format!(
+            "void {wrapper}({params}) {{ {name}({args}); }}\n",
+            wrapper = wrapper_name,
+            params = params_decl(g),
+            name = g.name,
+            args = args(g)
+        ));
+    } else {
+        c_source.push_str(&format!(
+            "{ret} {wrapper}({params}) {{ return {name}({args}); }}\n",
+            ret = g.ret_ty,
+            wrapper = wrapper_name,
+            params = params_decl(g),
+            name = g.name,
+            args = args(g)
+        ));
+    }
+
+    let rust_decl = if is_void {
+        format!("pub fn {}({});", wrapper_name, rust_params_decl(g))
+    } else {
+        format!(
+            "pub fn {}({}) -> {};",
+            wrapper_name,
+            rust_params_decl(g),
+            g.ret_ty
+        )
+    };
+
+    Wrapper { c_source, rust_decl }
+}
+
+/// Wraps every `static`/`static inline` function among `functions`,
+/// writing the combined C source to `path`. Returns the Rust FFI
+/// declarations that bind to the generated wrappers.
+pub fn wrap_all_to_file<P: AsRef<Path>>(
+    functions: &[Global],
+    suffix: &str,
+    path: P,
+) -> io::Result<Vec<String>> {
+    let mut c_source = String::new();
+    let mut decls = Vec::new();
+    for f in functions.iter().filter(|f| f.is_static_inline) {
+        let wrapper = wrap(f, suffix);
+        c_source.push_str(&wrapper.c_source);
+        decls.push(wrapper.rust_decl);
+    }
+    fs::write(path, c_source)?;
+    Ok(decls)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ir::{Global, ItemKind, Param};
+
+    fn static_inline_fn() -> Global {
+        let mut g = Global::new(ItemKind::Function, "square", "test.h");
+        g.is_static_inline = true;
+        g.ret_ty = "int".to_string();
+        g.params = vec![Param { name: "x".into(), ty: "int".into() }];
+        g
+    }
+
+    #[test]
+    fn wraps_non_void_function() {
+        let w = wrap(&static_inline_fn(), "__extern");
+        assert_eq!(
+            w.c_source,
+            "int square__extern(int x) { return square(x); }\n"
+        );
+        assert_eq!(w.rust_decl, "pub fn square__extern(x: int) -> int;");
+    }
+
+    #[test]
+    fn wraps_void_function_without_return() {
+        let mut g = static_inline_fn();
+        g.ret_ty = "void".to_string();
+        let w = wrap(&g, "__extern");
+        assert_eq!(w.c_source, "void square__extern(int x) { square(x); }\n");
+        assert_eq!(w.rust_decl, "pub fn square__extern(x: int);");
+    }
+}
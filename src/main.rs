@@ -31,6 +31,26 @@ Options:
                                whose name contains <name>
                                If multiple -match options are provided, files
                                matching any rule are bound to.
+  --allowlist-function=<regex> Only output bindings for functions whose name
+                               matches <regex> (full match). Can be provided
+                               multiple times. Types reachable from an
+                               allowlisted item are pulled in automatically.
+  --allowlist-type=<regex>     Only output bindings for types whose name
+                               matches <regex> (full match). Can be provided
+                               multiple times.
+  --allowlist-var=<regex>      Only output bindings for variables whose name
+                               matches <regex> (full match). Can be provided
+                               multiple times.
+  --blocklist-type=<regex>     Mark types matching <regex> as opaque and drop
+                               their bindings. Can be provided multiple times.
+  --blocklist-function=<regex> Don't output bindings for functions matching
+                               <regex>. Can be provided multiple times.
+  --blocklist-item=<regex>     Don't output bindings for any item (type,
+                               function or variable) matching <regex>. Can be
+                               provided multiple times.
+  --blocklist-file=<regex>     Don't output bindings for anything defined in a
+                               file whose path matches <regex>. Can be
+                               provided multiple times.
   --builtins                   Output bindings for builtin definitions
                                (for example __builtin_va_list)
   --emit-clang-ast             Output the ast (for debugging purposes)
@@ -54,6 +74,14 @@ Options:
                               `libfoo_`. The removal is case-insensitive.
   --no-derive-debug           Disable `derive(Debug)` for all generated types.
   --no-rust-enums             Convert C enums to Rust constants instead of enums.
+  --newtype-enum=<regex>      Make C enums whose name matches <regex> into a
+                              newtype struct with associated consts, so that
+                              values not listed in the C definition are still
+                              valid. Can be provided multiple times.
+  --bitfield-enum=<regex>     Like `--newtype-enum`, but also implements
+                              `BitOr`/`BitAnd` for the generated type, for
+                              enums that are really used as bitflags. Can be
+                              provided multiple times.
   --dont-convert-floats       Disables the convertion of C `float` and `double`
                               to Rust `f32` and `f64`.
   --convert-macros            Try to convert macros into const definitions
@@ -61,8 +89,41 @@ Options:
                               would fit in a u8,u16,u32,u64,i8,i16,i32,i64 to
                               the corresponding named C type, respectively. See
                               `--override-enum-type` for the type names.
-  --allow-unknown-types       Don't fail generation on stumbling upon an unknown type, 
+  --allow-unknown-types       Don't fail generation on stumbling upon an unknown type,
                               issue a warning and continue.
+  --depfile=<path>            Write a `make`-compatible depfile to <path>,
+                              listing every header transitively included
+                              while parsing <file>, so build systems know to
+                              re-run bindgen when any of them changes.
+  --wrap-static-fns           Generate non-inline C wrapper functions for
+                              `static`/`static inline` functions and bind to
+                              those instead, so they become callable. The
+                              wrapper source is written to the path given by
+                              `--wrap-static-fns-path` and must be compiled
+                              and linked in alongside the crate.
+  --wrap-static-fns-path=<path>  Path to write the generated C wrapper source
+                              to. Only meaningful with `--wrap-static-fns`.
+                              [default: bindgen/extern.c]
+  --wrap-static-fns-suffix=<suffix>  Suffix appended to the name of each
+                              static function to produce its wrapper symbol.
+                              Only meaningful with `--wrap-static-fns`.
+                              [default: __extern]
+  --merge-extern-blocks       Merge consecutive `extern \"C\" { ... }` blocks
+                              that share the same ABI into a single block.
+  --sort-semantically         Reorder the generated top-level items into a
+                              stable order (types, then constants, then
+                              functions, then statics), alphabetically within
+                              each category, so regenerating bindings
+                              produces a stable diff.
+  --dynamic-loading=<name>    Require the library to be loaded dynamically at
+                              runtime instead of linked against, generating a
+                              struct named <name> that holds a
+                              `libloading::Library` and one function pointer
+                              per allowlisted function, loaded via `dlopen`.
+  --dynamic-link-require-all  Require that every function be present in the
+                              dynamic library, rather than allowing missing
+                              symbols. Only meaningful with
+                              `--dynamic-loading`.
 ";
 
 #[derive(Debug, RustcDecodable)]
@@ -72,6 +133,13 @@ struct Args {
     flag_link: Option<String>,
     flag_output: String,
     flag_match: Vec<String>,
+    flag_allowlist_function: Vec<String>,
+    flag_allowlist_type: Vec<String>,
+    flag_allowlist_var: Vec<String>,
+    flag_blocklist_type: Vec<String>,
+    flag_blocklist_function: Vec<String>,
+    flag_blocklist_item: Vec<String>,
+    flag_blocklist_file: Vec<String>,
     flag_builtins: bool,
     flag_emit_clang_ast: bool,
     flag_override_enum_type: String,
@@ -81,12 +149,27 @@ struct Args {
     // TODO: allow finer control.
     flag_no_derive_debug: bool,
     flag_no_rust_enums: bool,
+    flag_newtype_enum: Vec<String>,
+    flag_bitfield_enum: Vec<String>,
     flag_dont_convert_floats: bool,
     flag_convert_macros: bool,
     flag_macro_int_types: Option<String>,
     flag_allow_unknown_types: bool,
+    flag_depfile: Option<String>,
+    flag_dynamic_loading: Option<String>,
+    flag_dynamic_link_require_all: bool,
+    flag_wrap_static_fns: bool,
+    flag_wrap_static_fns_path: String,
+    flag_wrap_static_fns_suffix: String,
+    flag_merge_extern_blocks: bool,
+    flag_sort_semantically: bool,
 }
 
+// Note: `ParseCallbacks` (renaming hooks, enum variant naming, derive
+// injection, macro-to-`IntKind` overrides) is a `Builder` extension point
+// for crates embedding `bindgen` as a library. It has no CLI flag of its
+// own -- there is no way to hand a trait implementation across a command
+// line -- so this binary has nothing to wire up for it.
 fn args_to_opts(args: Args) -> Builder<'static> {
     let mut builder = Builder::new(args.arg_file);
     builder.emit_ast(args.flag_emit_clang_ast)
@@ -105,6 +188,33 @@ fn args_to_opts(args: Args) -> Builder<'static> {
     for flag_match in args.flag_match {
         builder.match_pat(flag_match);
     }
+    for regex in args.flag_allowlist_function {
+        builder.allowlist_function(regex);
+    }
+    for regex in args.flag_allowlist_type {
+        builder.allowlist_type(regex);
+    }
+    for regex in args.flag_allowlist_var {
+        builder.allowlist_var(regex);
+    }
+    for regex in args.flag_blocklist_type {
+        builder.blocklist_type(regex);
+    }
+    for regex in args.flag_blocklist_function {
+        builder.blocklist_function(regex);
+    }
+    for regex in args.flag_blocklist_item {
+        builder.blocklist_item(regex);
+    }
+    for regex in args.flag_blocklist_file {
+        builder.blocklist_file(regex);
+    }
+    for regex in args.flag_newtype_enum {
+        builder.newtype_enum(regex);
+    }
+    for regex in args.flag_bitfield_enum {
+        builder.bitfield_enum(regex);
+    }
     if let Some(s) = args.flag_remove_prefix {
         builder.remove_prefix(s);
     }
@@ -143,6 +253,21 @@ fn args_to_opts(args: Args) -> Builder<'static> {
         };
         builder.link(lib, kind);
     }
+    if let Some(name) = args.flag_dynamic_loading {
+        builder.dynamic_library_name(name);
+        builder.dynamic_link_require_all(args.flag_dynamic_link_require_all);
+    }
+    if args.flag_wrap_static_fns {
+        builder.wrap_static_fns(true)
+               .wrap_static_fns_path(args.flag_wrap_static_fns_path)
+               .wrap_static_fns_suffix(args.flag_wrap_static_fns_suffix);
+    }
+    if args.flag_merge_extern_blocks {
+        builder.merge_extern_blocks(true);
+    }
+    if args.flag_sort_semantically {
+        builder.sort_semantically(true);
+    }
     builder
 }
 
@@ -163,12 +288,20 @@ pub fn main() {
     debug!("{:?}", args);
 
     let output = get_output(&args.flag_output);
+    let output_path = args.flag_output.clone();
+    let depfile = args.flag_depfile.clone();
 
     let builder = args_to_opts(args);
     debug!("{:?}", builder);
 
     match builder.generate() {
         Ok(bindings) => {
+            if let Some(depfile) = depfile {
+                if let Err(e) = bindings.write_depfile(&depfile, &output_path) {
+                    error!("Unable to write depfile to file. {}", e);
+                    exit(-1);
+                }
+            }
             match bindings.write(output) {
                 Ok(()) => (),
                 Err(e) => {